@@ -0,0 +1,13 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_build::configure().build_server(true).build_client(false).compile(
+        &[
+            "proto/sandbox.proto",
+            "proto/syscall.proto",
+            "proto/introspection.proto",
+            "proto/file_transfer.proto",
+            "proto/events.proto",
+        ],
+        &["proto"],
+    )?;
+    Ok(())
+}
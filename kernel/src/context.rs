@@ -0,0 +1,75 @@
+//! Request-scoped deadline and cancellation, threaded from the gRPC edge
+//! into syscall execution so a caller that times out or disconnects
+//! doesn't leave the kernel doing work for no one.
+
+use std::future::Future;
+use std::time::Instant;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::syscall::SyscallResult;
+
+/// Carries the deadline and cancellation signal for a single syscall
+/// invocation.
+#[derive(Clone)]
+pub struct SyscallContext {
+    pub deadline: Option<Instant>,
+    pub cancel: CancellationToken,
+}
+
+impl SyscallContext {
+    pub fn new() -> Self {
+        Self {
+            deadline: None,
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.deadline.map(|d| Instant::now() >= d).unwrap_or(false)
+    }
+
+    /// Races `fut` against cancellation and the deadline (if any),
+    /// returning the appropriate `SyscallResult` the moment either fires.
+    /// Long-running syscall arms should run their work through this
+    /// instead of awaiting it directly.
+    pub async fn guard<T>(&self, fut: impl Future<Output = T>) -> Result<T, SyscallResult> {
+        if self.cancel.is_cancelled() {
+            return Err(SyscallResult::Cancelled);
+        }
+        if self.is_expired() {
+            return Err(SyscallResult::DeadlineExceeded);
+        }
+
+        match self.deadline {
+            Some(deadline) => {
+                tokio::select! {
+                    _ = self.cancel.cancelled() => Err(SyscallResult::Cancelled),
+                    _ = tokio::time::sleep_until(tokio::time::Instant::from_std(deadline)) => {
+                        Err(SyscallResult::DeadlineExceeded)
+                    }
+                    out = fut => Ok(out),
+                }
+            }
+            None => {
+                tokio::select! {
+                    _ = self.cancel.cancelled() => Err(SyscallResult::Cancelled),
+                    out = fut => Ok(out),
+                }
+            }
+        }
+    }
+}
+
+impl Default for SyscallContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,111 @@
+//! `KernelEvents` gRPC service: a server-streaming feed of kernel
+//! lifecycle events, so the AI service can react to process/sandbox/
+//! memory changes in real time instead of polling the Introspection
+//! service. Each subscriber gets its own `broadcast::Receiver`; a
+//! subscriber that falls behind has old events dropped out from under it
+//! rather than stalling the publishers, so the feed never applies
+//! backpressure to the kernel itself.
+
+use std::pin::Pin;
+
+use tokio::sync::broadcast;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+
+use crate::events::{EventBus, EventFilter, EventKind, EventKindTag, KernelEvent};
+
+pub mod proto {
+    tonic::include_proto!("aios.events.v1");
+}
+
+use proto::event::Kind as ProtoKind;
+use proto::kernel_events_server::KernelEvents;
+use proto::{
+    CapabilityDenied, Event, MemoryPressure, ProcessCreated, ProcessExited, SandboxStateChanged,
+    SubscribeEventsRequest,
+};
+
+pub struct EventService {
+    event_bus: EventBus,
+}
+
+impl EventService {
+    pub fn new(event_bus: EventBus) -> Self {
+        Self { event_bus }
+    }
+}
+
+fn parse_kind(name: &str) -> Option<EventKindTag> {
+    match name {
+        "process_created" => Some(EventKindTag::ProcessCreated),
+        "process_exited" => Some(EventKindTag::ProcessExited),
+        "sandbox_state_changed" => Some(EventKindTag::SandboxStateChanged),
+        "capability_denied" => Some(EventKindTag::CapabilityDenied),
+        "memory_pressure" => Some(EventKindTag::MemoryPressure),
+        _ => None,
+    }
+}
+
+fn to_proto(event: KernelEvent) -> Event {
+    let kind = match event.kind {
+        EventKind::ProcessCreated { pid } => ProtoKind::ProcessCreated(ProcessCreated { pid }),
+        EventKind::ProcessExited { os_pid, exit_code } => {
+            ProtoKind::ProcessExited(ProcessExited { os_pid, exit_code })
+        }
+        EventKind::SandboxStateChanged { sandbox_id, state } => {
+            ProtoKind::SandboxStateChanged(SandboxStateChanged { sandbox_id, state: state.to_string() })
+        }
+        EventKind::CapabilityDenied { pid, syscall, reason } => {
+            ProtoKind::CapabilityDenied(CapabilityDenied { pid, syscall: syscall.to_string(), reason })
+        }
+        EventKind::MemoryPressure { allocated_bytes } => {
+            ProtoKind::MemoryPressure(MemoryPressure { allocated_bytes })
+        }
+    };
+    Event { sequence: event.sequence, kind: Some(kind) }
+}
+
+#[tonic::async_trait]
+impl KernelEvents for EventService {
+    type SubscribeEventsStream = Pin<Box<dyn Stream<Item = Result<Event, Status>> + Send>>;
+
+    async fn subscribe_events(
+        &self,
+        request: Request<SubscribeEventsRequest>,
+    ) -> Result<Response<Self::SubscribeEventsStream>, Status> {
+        let req = request.into_inner();
+        let mut kinds = Vec::new();
+        for name in &req.kinds {
+            match parse_kind(name) {
+                Some(tag) => kinds.push(tag),
+                None => return Err(Status::invalid_argument(format!("unknown event kind `{name}`"))),
+            }
+        }
+        let filter = EventFilter {
+            kinds: (!kinds.is_empty()).then_some(kinds),
+            pid: req.pid,
+        };
+
+        let mut receiver = self.event_bus.subscribe();
+        let (tx, rx) = tokio::sync::mpsc::channel(64);
+
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        if filter.matches(&event) && tx.send(Ok(to_proto(event))).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(dropped)) => {
+                        log::warn!("event subscriber lagged, {dropped} events dropped");
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(tokio_stream::wrappers::ReceiverStream::new(rx))))
+    }
+}
@@ -0,0 +1,233 @@
+//! Streaming file I/O: `ReadFileStream` (server-streaming) and
+//! `WriteFileStream` (client-streaming), for payloads too large to move
+//! in a single gRPC message. The sandbox path allowlist is re-checked on
+//! every chunk, not just once up front, since a sandbox's allowed paths
+//! can change while a stream is still open. A bounded channel caps how
+//! many chunks `ReadFileStream` will buffer ahead of a slow consumer.
+
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::memory::MemoryManager;
+use crate::sandbox::{Capability, SandboxManager};
+
+pub mod proto {
+    tonic::include_proto!("aios.filetransfer.v1");
+}
+
+use proto::file_transfer_server::FileTransfer;
+use proto::write_file_stream_request::Payload;
+use proto::{FileChunk, ReadFileStreamRequest, WriteFileStreamRequest, WriteFileStreamResponse};
+
+/// Metadata key callers set to request per-chunk gzip compression. This
+/// is independent of tonic's own transport-level compression: it governs
+/// the `FileChunk.data` payload itself, so a consumer can decompress one
+/// chunk at a time as it arrives.
+const COMPRESSION_METADATA_KEY: &str = "x-chunk-compression";
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+pub struct FileTransferService {
+    sandbox_manager: SandboxManager,
+    memory_manager: MemoryManager,
+    chunk_size: usize,
+    max_in_flight_chunks: usize,
+}
+
+impl FileTransferService {
+    pub fn new(sandbox_manager: SandboxManager, memory_manager: MemoryManager, max_in_flight_chunks: usize) -> Self {
+        Self {
+            sandbox_manager,
+            memory_manager,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_in_flight_chunks,
+        }
+    }
+}
+
+fn wants_compression(metadata: &MetadataMap, disable_compression: bool) -> bool {
+    if disable_compression {
+        return false;
+    }
+    metadata
+        .get(COMPRESSION_METADATA_KEY)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.eq_ignore_ascii_case("gzip"))
+        .unwrap_or(false)
+}
+
+fn compress_chunk(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+fn decompress_chunk(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut decoder = GzDecoder::new(data);
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out)?;
+    Ok(out)
+}
+
+#[tonic::async_trait]
+impl FileTransfer for FileTransferService {
+    type ReadFileStreamStream = Pin<Box<dyn Stream<Item = Result<FileChunk, Status>> + Send>>;
+
+    async fn read_file_stream(
+        &self,
+        request: Request<ReadFileStreamRequest>,
+    ) -> Result<Response<Self::ReadFileStreamStream>, Status> {
+        let compress = wants_compression(request.metadata(), request.get_ref().disable_compression);
+        let req = request.into_inner();
+
+        let config = self
+            .sandbox_manager
+            .get_config(req.pid)
+            .ok_or_else(|| Status::permission_denied(format!("no sandbox for pid {}", req.pid)))?;
+        if !config.has_capability(Capability::ReadFile) {
+            return Err(Status::permission_denied("missing ReadFile capability"));
+        }
+        let path = PathBuf::from(req.path);
+        if !config.is_path_allowed(&path) {
+            return Err(Status::permission_denied(format!("{} is outside the sandbox", path.display())));
+        }
+
+        let sandbox_manager = self.sandbox_manager.clone();
+        let memory_manager = self.memory_manager.clone();
+        let pid = req.pid;
+        let chunk_size = self.chunk_size;
+        let (tx, rx) = mpsc::channel(self.max_in_flight_chunks);
+
+        tokio::spawn(async move {
+            let mut file = match tokio::fs::File::open(&path).await {
+                Ok(f) => f,
+                Err(e) => {
+                    let _ = tx.send(Err(Status::not_found(e.to_string()))).await;
+                    return;
+                }
+            };
+            let mut buf = vec![0u8; chunk_size];
+            loop {
+                let Some(config) = sandbox_manager.get_config(pid) else {
+                    let _ = tx.send(Err(Status::permission_denied("sandbox removed mid-stream"))).await;
+                    break;
+                };
+                if !config.is_path_allowed(&path) {
+                    let _ = tx
+                        .send(Err(Status::permission_denied(format!("{} is outside the sandbox", path.display()))))
+                        .await;
+                    break;
+                }
+                match file.read(&mut buf).await {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        let chunk_result = if compress {
+                            compress_chunk(&buf[..n]).map_err(|e| Status::internal(e.to_string()))
+                        } else {
+                            Ok(buf[..n].to_vec())
+                        };
+                        let item = chunk_result.map(|data| {
+                            // Counted while the chunk waits for room in
+                            // the bounded channel behind a possibly slow
+                            // consumer; freed as soon as the channel
+                            // accepts it, so chunks already queued in the
+                            // channel are not counted.
+                            memory_manager.record_allocation(data.len() as u64);
+                            FileChunk { data, compressed: compress }
+                        });
+                        let freed = item.as_ref().ok().map(|c| c.data.len() as u64);
+                        let sent = tx.send(item).await.is_err();
+                        if let Some(bytes) = freed {
+                            memory_manager.record_free(bytes);
+                        }
+                        if sent {
+                            // Receiver dropped: the caller went away, stop reading.
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(Status::internal(e.to_string()))).await;
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+
+    async fn write_file_stream(
+        &self,
+        request: Request<Streaming<WriteFileStreamRequest>>,
+    ) -> Result<Response<WriteFileStreamResponse>, Status> {
+        let mut stream = request.into_inner();
+
+        let header = match stream.message().await? {
+            Some(WriteFileStreamRequest { payload: Some(Payload::Header(header)) }) => header,
+            Some(_) => return Err(Status::invalid_argument("first message must be a header")),
+            None => return Err(Status::invalid_argument("empty stream")),
+        };
+
+        let config = self
+            .sandbox_manager
+            .get_config(header.pid)
+            .ok_or_else(|| Status::permission_denied(format!("no sandbox for pid {}", header.pid)))?;
+        if !config.has_capability(Capability::WriteFile) {
+            return Err(Status::permission_denied("missing WriteFile capability"));
+        }
+        let path = PathBuf::from(header.path);
+        if !config.is_path_allowed(&path) {
+            return Err(Status::permission_denied(format!("{} is outside the sandbox", path.display())));
+        }
+
+        let mut file = tokio::fs::File::create(&path)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let mut bytes_written = 0u64;
+
+        while let Some(message) = stream.message().await? {
+            let chunk = match message.payload {
+                Some(Payload::Chunk(chunk)) => chunk,
+                Some(Payload::Header(_)) => return Err(Status::invalid_argument("unexpected second header")),
+                None => continue,
+            };
+
+            // Re-check on every chunk so a sandbox reconfigured mid-upload
+            // can't be used to smuggle data past a path that was allowed
+            // when the stream opened but isn't anymore.
+            let config = self
+                .sandbox_manager
+                .get_config(header.pid)
+                .ok_or_else(|| Status::permission_denied("sandbox removed mid-stream"))?;
+            if !config.is_path_allowed(&path) {
+                return Err(Status::permission_denied(format!("{} is outside the sandbox", path.display())));
+            }
+
+            let data = if chunk.compressed {
+                decompress_chunk(&chunk.data).map_err(|e| Status::invalid_argument(e.to_string()))?
+            } else {
+                chunk.data
+            };
+            // Counted while the decompressed chunk is held in memory
+            // before being flushed to disk, freed as soon as the write
+            // completes.
+            self.memory_manager.record_allocation(data.len() as u64);
+            let write_result = file.write_all(&data).await;
+            self.memory_manager.record_free(data.len() as u64);
+            write_result.map_err(|e| Status::internal(e.to_string()))?;
+            bytes_written += data.len() as u64;
+        }
+
+        Ok(Response::new(WriteFileStreamResponse { bytes_written }))
+    }
+}
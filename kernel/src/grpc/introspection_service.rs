@@ -0,0 +1,145 @@
+//! `Introspection` gRPC service: a channelz-style window into live kernel
+//! state, so the AI service (or a human debugging it) can enumerate
+//! processes, sandboxes, IPC channels, and memory usage without guessing
+//! IDs.
+
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+
+use crate::interceptor::AuditInterceptor;
+use crate::ipc::IPCManager;
+use crate::memory::MemoryManager;
+use crate::process::ProcessManager;
+use crate::sandbox::{Sandbox, SandboxManager};
+
+pub mod proto {
+    tonic::include_proto!("aios.introspection.v1");
+}
+
+use proto::introspection_server::Introspection;
+use proto::{
+    GetMemoryStatsRequest, GetProcessRequest, GetSandboxRequest, ListIpcChannelsRequest,
+    ListIpcChannelsResponse, ListProcessesRequest, ListProcessesResponse, ListSandboxesRequest,
+    ListSandboxesResponse, MemoryStatsView, ProcessView, SandboxView,
+};
+
+pub struct IntrospectionService {
+    process_manager: ProcessManager,
+    sandbox_manager: SandboxManager,
+    ipc_manager: IPCManager,
+    memory_manager: MemoryManager,
+    audit: Arc<AuditInterceptor>,
+}
+
+impl IntrospectionService {
+    pub fn new(
+        process_manager: ProcessManager,
+        sandbox_manager: SandboxManager,
+        ipc_manager: IPCManager,
+        memory_manager: MemoryManager,
+        audit: Arc<AuditInterceptor>,
+    ) -> Self {
+        Self {
+            process_manager,
+            sandbox_manager,
+            ipc_manager,
+            memory_manager,
+            audit,
+        }
+    }
+
+    fn process_view(&self, view: crate::process::ProcessView) -> ProcessView {
+        let sandbox = self.sandbox_manager.get_config(view.pid);
+        let stats = self.memory_manager.stats();
+        ProcessView {
+            pid: view.pid,
+            name: view.name,
+            priority: view.priority as u32,
+            capabilities: sandbox
+                .as_ref()
+                .map(|c| c.capabilities.iter().map(|cap| format!("{cap:?}")).collect())
+                .unwrap_or_default(),
+            allowed_paths: sandbox
+                .as_ref()
+                .map(|c| c.allowed_paths.iter().map(|p| p.display().to_string()).collect())
+                .unwrap_or_default(),
+            memory_allocated_bytes: stats.allocated_bytes,
+            recent_syscall_count: self.audit.syscall_count(view.pid),
+        }
+    }
+
+    fn sandbox_view(sandbox: Sandbox) -> SandboxView {
+        SandboxView {
+            sandbox_id: sandbox.id,
+            pid: sandbox.config.pid,
+            state: sandbox.state.as_str().to_string(),
+            capabilities: sandbox.config.capabilities.iter().map(|c| format!("{c:?}")).collect(),
+            allowed_paths: sandbox
+                .config
+                .allowed_paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect(),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Introspection for IntrospectionService {
+    async fn list_processes(
+        &self,
+        _request: Request<ListProcessesRequest>,
+    ) -> Result<Response<ListProcessesResponse>, Status> {
+        let processes = self.process_manager.list().into_iter().map(|p| self.process_view(p)).collect();
+        Ok(Response::new(ListProcessesResponse { processes }))
+    }
+
+    async fn get_process(&self, request: Request<GetProcessRequest>) -> Result<Response<ProcessView>, Status> {
+        let pid = request.into_inner().pid;
+        let view = self
+            .process_manager
+            .get(pid)
+            .ok_or_else(|| Status::not_found(format!("no process with pid {pid}")))?;
+        Ok(Response::new(self.process_view(view)))
+    }
+
+    async fn list_sandboxes(
+        &self,
+        _request: Request<ListSandboxesRequest>,
+    ) -> Result<Response<ListSandboxesResponse>, Status> {
+        let sandboxes = self.sandbox_manager.list().into_iter().map(Self::sandbox_view).collect();
+        Ok(Response::new(ListSandboxesResponse { sandboxes }))
+    }
+
+    async fn get_sandbox(&self, request: Request<GetSandboxRequest>) -> Result<Response<SandboxView>, Status> {
+        let sandbox_id = request.into_inner().sandbox_id;
+        let sandbox = self
+            .sandbox_manager
+            .get(&sandbox_id)
+            .ok_or_else(|| Status::not_found(format!("no sandbox `{sandbox_id}`")))?;
+        Ok(Response::new(Self::sandbox_view(sandbox)))
+    }
+
+    async fn list_ipc_channels(
+        &self,
+        _request: Request<ListIpcChannelsRequest>,
+    ) -> Result<Response<ListIpcChannelsResponse>, Status> {
+        // Always empty today: nothing calls `IPCManager::create_channel`
+        // yet, since channel creation isn't exposed over a syscall or
+        // gRPC service. This faithfully reports the (currently unused)
+        // registry rather than synthesizing channels that don't exist.
+        Ok(Response::new(ListIpcChannelsResponse {
+            channel_names: self.ipc_manager.channel_names(),
+        }))
+    }
+
+    async fn get_memory_stats(
+        &self,
+        _request: Request<GetMemoryStatsRequest>,
+    ) -> Result<Response<MemoryStatsView>, Status> {
+        Ok(Response::new(MemoryStatsView {
+            allocated_bytes: self.memory_manager.stats().allocated_bytes,
+        }))
+    }
+}
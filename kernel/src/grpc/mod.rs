@@ -0,0 +1,73 @@
+//! gRPC front door for the kernel. The AI service talks to the kernel
+//! exclusively through the services wired up here.
+
+mod event_service;
+mod file_transfer_service;
+mod introspection_service;
+mod sandbox_service;
+mod syscall_service;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tonic::transport::Server;
+
+use crate::events::EventBus;
+use crate::interceptor::AuditInterceptor;
+use crate::ipc::IPCManager;
+use crate::memory::MemoryManager;
+use crate::process::ProcessManager;
+use crate::sandbox::SandboxManager;
+use crate::syscall::SyscallExecutor;
+
+use event_service::proto::kernel_events_server::KernelEventsServer;
+use event_service::EventService;
+use file_transfer_service::proto::file_transfer_server::FileTransferServer;
+use file_transfer_service::FileTransferService;
+use introspection_service::proto::introspection_server::IntrospectionServer;
+use introspection_service::IntrospectionService;
+use sandbox_service::proto::sandbox_lifecycle_server::SandboxLifecycleServer;
+use sandbox_service::SandboxLifecycleService;
+use syscall_service::proto::syscall_service_server::SyscallServiceServer;
+use syscall_service::SyscallGrpcService;
+
+/// Chunks a `ReadFileStream` will buffer ahead of a consumer that hasn't
+/// acknowledged them yet, bounding how much of a large file the kernel
+/// can end up holding in memory for one slow reader.
+const DEFAULT_MAX_IN_FLIGHT_CHUNKS: usize = 16;
+
+/// Starts the kernel's gRPC server, serving until the process exits or the
+/// server errors.
+pub async fn start_grpc_server(
+    addr: SocketAddr,
+    syscall_executor: SyscallExecutor,
+    process_manager: ProcessManager,
+    sandbox_manager: SandboxManager,
+    ipc_manager: IPCManager,
+    memory_manager: MemoryManager,
+    audit: Arc<AuditInterceptor>,
+    event_bus: EventBus,
+) -> Result<(), tonic::transport::Error> {
+    let sandbox_lifecycle =
+        SandboxLifecycleService::new(sandbox_manager.clone(), process_manager.clone(), memory_manager.clone());
+    let syscalls = SyscallGrpcService::new(syscall_executor);
+    let file_transfer =
+        FileTransferService::new(sandbox_manager.clone(), memory_manager.clone(), DEFAULT_MAX_IN_FLIGHT_CHUNKS);
+    let introspection = IntrospectionService::new(
+        process_manager,
+        sandbox_manager,
+        ipc_manager,
+        memory_manager,
+        audit,
+    );
+    let events = EventService::new(event_bus);
+
+    Server::builder()
+        .add_service(SandboxLifecycleServer::new(sandbox_lifecycle))
+        .add_service(SyscallServiceServer::new(syscalls))
+        .add_service(FileTransferServer::new(file_transfer))
+        .add_service(IntrospectionServer::new(introspection))
+        .add_service(KernelEventsServer::new(events))
+        .serve(addr)
+        .await
+}
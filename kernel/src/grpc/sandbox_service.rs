@@ -0,0 +1,179 @@
+//! `SandboxLifecycle` gRPC service: create/start/stop/wait/delete a
+//! sandbox remotely instead of only through the in-process demo path.
+
+use std::time::Duration;
+
+use tonic::{Request, Response, Status};
+
+use crate::memory::MemoryManager;
+use crate::process::ProcessManager;
+use crate::sandbox::{Capability, SandboxConfig, SandboxManager};
+
+pub mod proto {
+    tonic::include_proto!("aios.sandbox.v1");
+}
+
+use proto::sandbox_lifecycle_server::SandboxLifecycle;
+use proto::{
+    CreateSandboxRequest, CreateSandboxResponse, DeleteSandboxRequest, DeleteSandboxResponse,
+    SandboxStatusRequest, SandboxStatusResponse, StartSandboxRequest, StartSandboxResponse,
+    StopSandboxRequest, StopSandboxResponse, WaitSandboxRequest, WaitSandboxResponse,
+};
+
+pub struct SandboxLifecycleService {
+    sandbox_manager: SandboxManager,
+    process_manager: ProcessManager,
+    memory_manager: MemoryManager,
+}
+
+impl SandboxLifecycleService {
+    pub fn new(sandbox_manager: SandboxManager, process_manager: ProcessManager, memory_manager: MemoryManager) -> Self {
+        Self { sandbox_manager, process_manager, memory_manager }
+    }
+}
+
+fn parse_capability(name: &str) -> Option<Capability> {
+    match name {
+        "ReadFile" => Some(Capability::ReadFile),
+        "WriteFile" => Some(Capability::WriteFile),
+        "SpawnProcess" => Some(Capability::SpawnProcess),
+        "NetworkAccess" => Some(Capability::NetworkAccess),
+        "SystemInfo" => Some(Capability::SystemInfo),
+        _ => None,
+    }
+}
+
+fn capability_name(capability: Capability) -> &'static str {
+    match capability {
+        Capability::ReadFile => "ReadFile",
+        Capability::WriteFile => "WriteFile",
+        Capability::SpawnProcess => "SpawnProcess",
+        Capability::NetworkAccess => "NetworkAccess",
+        Capability::SystemInfo => "SystemInfo",
+    }
+}
+
+#[tonic::async_trait]
+impl SandboxLifecycle for SandboxLifecycleService {
+    async fn create_sandbox(
+        &self,
+        request: Request<CreateSandboxRequest>,
+    ) -> Result<Response<CreateSandboxResponse>, Status> {
+        let req = request.into_inner();
+        let mut config = SandboxConfig::new(req.pid);
+        for name in &req.capabilities {
+            match parse_capability(name) {
+                Some(capability) => {
+                    config.grant(capability);
+                }
+                None => return Err(Status::invalid_argument(format!("unknown capability `{name}`"))),
+            }
+        }
+        for path in req.allowed_paths {
+            config.allow_path(path.into());
+        }
+        let sandbox_id = self.sandbox_manager.create_sandbox(config);
+        Ok(Response::new(CreateSandboxResponse { sandbox_id }))
+    }
+
+    async fn start_sandbox(
+        &self,
+        request: Request<StartSandboxRequest>,
+    ) -> Result<Response<StartSandboxResponse>, Status> {
+        let req = request.into_inner();
+        self.sandbox_manager
+            .start(&req.sandbox_id)
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+        Ok(Response::new(StartSandboxResponse {}))
+    }
+
+    async fn stop_sandbox(
+        &self,
+        request: Request<StopSandboxRequest>,
+    ) -> Result<Response<StopSandboxResponse>, Status> {
+        let req = request.into_inner();
+        let sandbox = self
+            .sandbox_manager
+            .get(&req.sandbox_id)
+            .ok_or_else(|| Status::not_found(format!("no sandbox `{}`", req.sandbox_id)))?;
+
+        // If the sandbox's pid never actually spawned an OS process there's
+        // nothing to signal, and 0 marks a clean stop. One that already
+        // exited and was reaped isn't signalled again; `terminate` just
+        // returns its recorded exit code.
+        let exit_code = match self.process_manager.os_pid_for(sandbox.config.pid) {
+            Some(os_pid) => {
+                self.process_manager
+                    .terminate(os_pid, Duration::from_secs(req.timeout_secs as u64))
+                    .await
+            }
+            None => 0,
+        };
+
+        // `watch_process_exits` may have already recorded this stop (it
+        // raced `terminate` above and won); either way the sandbox ends up
+        // `Stopped` with the real exit code, so an `InvalidTransition` here
+        // isn't an error worth surfacing to the caller.
+        let _ = self.sandbox_manager.stop(&req.sandbox_id, exit_code);
+        Ok(Response::new(StopSandboxResponse { exit_code }))
+    }
+
+    async fn wait_sandbox(
+        &self,
+        request: Request<WaitSandboxRequest>,
+    ) -> Result<Response<WaitSandboxResponse>, Status> {
+        let req = request.into_inner();
+        let (exit_code, exited_at) = self
+            .sandbox_manager
+            .wait(&req.sandbox_id)
+            .await
+            .map_err(|e| Status::not_found(e.to_string()))?;
+        let exited_at_unix_secs = exited_at
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        Ok(Response::new(WaitSandboxResponse {
+            exit_code,
+            exited_at_unix_secs,
+        }))
+    }
+
+    async fn sandbox_status(
+        &self,
+        request: Request<SandboxStatusRequest>,
+    ) -> Result<Response<SandboxStatusResponse>, Status> {
+        let req = request.into_inner();
+        let sandbox = self
+            .sandbox_manager
+            .get(&req.sandbox_id)
+            .ok_or_else(|| Status::not_found(format!("no sandbox `{}`", req.sandbox_id)))?;
+        Ok(Response::new(SandboxStatusResponse {
+            sandbox_id: sandbox.id,
+            state: sandbox.state.as_str().to_string(),
+            capabilities: sandbox
+                .config
+                .capabilities
+                .iter()
+                .map(|c| capability_name(*c).to_string())
+                .collect(),
+            allowed_paths: sandbox
+                .config
+                .allowed_paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect(),
+            memory_allocated_bytes: self.memory_manager.stats().allocated_bytes,
+        }))
+    }
+
+    async fn delete_sandbox(
+        &self,
+        request: Request<DeleteSandboxRequest>,
+    ) -> Result<Response<DeleteSandboxResponse>, Status> {
+        let req = request.into_inner();
+        self.sandbox_manager
+            .delete(&req.sandbox_id)
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+        Ok(Response::new(DeleteSandboxResponse {}))
+    }
+}
@@ -0,0 +1,105 @@
+//! `SyscallService` gRPC service: the actual entry point the AI service
+//! uses to ask the kernel to do something on a sandboxed process's
+//! behalf. Reads the caller's deadline from the standard `grpc-timeout`
+//! metadata and ties cancellation to the request's own lifetime, so a
+//! client that times out or disconnects stops the kernel from continuing
+//! on its behalf.
+
+use std::time::{Duration, Instant};
+
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status};
+use tokio_util::sync::CancellationToken;
+
+use crate::context::SyscallContext;
+use crate::syscall::{Syscall, SyscallExecutor, SyscallResult};
+
+pub mod proto {
+    tonic::include_proto!("aios.syscall.v1");
+}
+
+use proto::execute_request::Syscall as ProtoSyscall;
+use proto::execute_response::Outcome;
+use proto::syscall_service_server::SyscallService;
+use proto::{ExecuteRequest, ExecuteResponse};
+
+pub struct SyscallGrpcService {
+    executor: SyscallExecutor,
+}
+
+impl SyscallGrpcService {
+    pub fn new(executor: SyscallExecutor) -> Self {
+        Self { executor }
+    }
+}
+
+#[tonic::async_trait]
+impl SyscallService for SyscallGrpcService {
+    async fn execute(
+        &self,
+        request: Request<ExecuteRequest>,
+    ) -> Result<Response<ExecuteResponse>, Status> {
+        let deadline = parse_grpc_timeout(request.metadata()).map(|d| Instant::now() + d);
+        let cancel = CancellationToken::new();
+        // Cancels `cancel` the moment this future is dropped, which
+        // happens when the client disconnects mid-call (tonic/hyper drop
+        // the handler future rather than letting it run to completion).
+        let _cancel_on_drop = cancel.clone().drop_guard();
+        let ctx = match deadline {
+            Some(deadline) => SyscallContext { deadline: Some(deadline), cancel },
+            None => SyscallContext { deadline: None, cancel },
+        };
+
+        let req = request.into_inner();
+        let syscall = to_syscall(req.syscall)
+            .ok_or_else(|| Status::invalid_argument("missing syscall payload"))?;
+
+        let result = self.executor.execute(req.pid, syscall, &ctx).await;
+        Ok(Response::new(to_proto_response(result)))
+    }
+}
+
+fn to_syscall(proto_syscall: Option<ProtoSyscall>) -> Option<Syscall> {
+    Some(match proto_syscall? {
+        ProtoSyscall::FileExists(m) => Syscall::FileExists { path: m.path.into() },
+        ProtoSyscall::ReadFile(m) => Syscall::ReadFile { path: m.path.into() },
+        ProtoSyscall::WriteFile(m) => Syscall::WriteFile {
+            path: m.path.into(),
+            data: m.data,
+        },
+        ProtoSyscall::SpawnProcess(m) => Syscall::SpawnProcess {
+            command: m.command,
+            args: m.args,
+        },
+        ProtoSyscall::WaitProcess(m) => Syscall::WaitProcess { pid: m.pid },
+        ProtoSyscall::GetSystemInfo(_) => Syscall::GetSystemInfo,
+    })
+}
+
+fn to_proto_response(result: SyscallResult) -> ExecuteResponse {
+    let outcome = match result {
+        SyscallResult::Success { data } => Outcome::Data(data.unwrap_or_default()),
+        SyscallResult::Error { message } => Outcome::Error(message),
+        SyscallResult::Cancelled => Outcome::Cancelled(true),
+        SyscallResult::DeadlineExceeded => Outcome::DeadlineExceeded(true),
+    };
+    ExecuteResponse { outcome: Some(outcome) }
+}
+
+/// Parses the gRPC-standard `grpc-timeout` header (e.g. `"500m"` for 500
+/// milliseconds, `"10S"` for 10 seconds) into a `Duration`.
+fn parse_grpc_timeout(metadata: &MetadataMap) -> Option<Duration> {
+    let value = metadata.get("grpc-timeout")?.to_str().ok()?;
+    let (digits, unit) = value.split_at(value.len().checked_sub(1)?);
+    let amount: u64 = digits.parse().ok()?;
+    let unit = unit.chars().next()?;
+    Some(match unit {
+        'H' => Duration::from_secs(amount * 3600),
+        'M' => Duration::from_secs(amount * 60),
+        'S' => Duration::from_secs(amount),
+        'm' => Duration::from_millis(amount),
+        'u' => Duration::from_micros(amount),
+        'n' => Duration::from_nanos(amount),
+        _ => return None,
+    })
+}
@@ -0,0 +1,96 @@
+//! Logs every syscall attempt and its outcome, and keeps a per-PID
+//! sliding-window history so the introspection service can report recent
+//! syscall activity without its own separate bookkeeping.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use log::info;
+
+use crate::process::Pid;
+use crate::syscall::{Syscall, SyscallResult};
+
+use super::{Denied, SyscallInterceptor};
+
+/// Width of the window `recent_syscall_count` reports over. Chosen to be
+/// wide enough to smooth over a single slow poll from the introspection
+/// client, not so wide that "recent" drifts into "since start".
+const RECENT_WINDOW: Duration = Duration::from_secs(60);
+
+pub struct AuditInterceptor {
+    /// Per-PID timestamps of every syscall seen, oldest first, trimmed to
+    /// `RECENT_WINDOW` on each access.
+    recent: Mutex<HashMap<Pid, VecDeque<Instant>>>,
+}
+
+impl AuditInterceptor {
+    pub fn new() -> Self {
+        Self {
+            recent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Number of syscalls recorded for `pid` in the last [`RECENT_WINDOW`].
+    pub fn syscall_count(&self, pid: Pid) -> u64 {
+        let mut recent = self.recent.lock().unwrap();
+        let std::collections::hash_map::Entry::Occupied(mut entry) = recent.entry(pid) else {
+            return 0;
+        };
+        trim(entry.get_mut());
+        let count = entry.get().len() as u64;
+        // PIDs aren't reused, so a quiet process's entry would otherwise
+        // sit here as an empty `VecDeque` forever; drop it once there's
+        // nothing left to trim.
+        if entry.get().is_empty() {
+            entry.remove();
+        }
+        count
+    }
+}
+
+/// Drops every timestamp older than `RECENT_WINDOW`. Entries are inserted
+/// in order, so the stale ones are always a prefix.
+fn trim(timestamps: &mut VecDeque<Instant>) {
+    let cutoff = Instant::now() - RECENT_WINDOW;
+    while matches!(timestamps.front(), Some(t) if *t < cutoff) {
+        timestamps.pop_front();
+    }
+}
+
+impl Default for AuditInterceptor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+impl SyscallInterceptor for AuditInterceptor {
+    fn before(&self, pid: Pid, syscall: &Syscall) -> Result<(), Denied> {
+        info!("audit ts={} pid={pid} syscall={} requested", now_unix_secs(), syscall.kind());
+        Ok(())
+    }
+
+    fn after(&self, pid: Pid, syscall: &Syscall, result: &SyscallResult) {
+        {
+            let mut recent = self.recent.lock().unwrap();
+            let timestamps = recent.entry(pid).or_default();
+            timestamps.push_back(Instant::now());
+            trim(timestamps);
+        }
+        let outcome = match result {
+            SyscallResult::Success { .. } => "allow",
+            SyscallResult::Error { .. } => "deny",
+            SyscallResult::Cancelled => "cancelled",
+            SyscallResult::DeadlineExceeded => "deadline_exceeded",
+        };
+        info!(
+            "audit ts={} pid={pid} syscall={} outcome={outcome}",
+            now_unix_secs(),
+            syscall.kind()
+        );
+    }
+}
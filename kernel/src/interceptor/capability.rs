@@ -0,0 +1,65 @@
+//! Consolidates the capability and path-allowlist checks that used to be
+//! duplicated across every `SyscallExecutor::execute` arm into a single
+//! interceptor.
+
+use std::path::Path;
+
+use crate::events::{EventBus, EventKind};
+use crate::process::Pid;
+use crate::sandbox::{Capability, SandboxManager};
+use crate::syscall::Syscall;
+
+use super::{Denied, SyscallInterceptor};
+
+pub struct CapabilityInterceptor {
+    sandbox_manager: SandboxManager,
+    event_bus: EventBus,
+}
+
+impl CapabilityInterceptor {
+    pub fn new(sandbox_manager: SandboxManager, event_bus: EventBus) -> Self {
+        Self { sandbox_manager, event_bus }
+    }
+
+    fn deny(&self, pid: Pid, syscall: &Syscall, reason: String) -> Denied {
+        self.event_bus.publish(EventKind::CapabilityDenied {
+            pid,
+            syscall: syscall.kind(),
+            reason: reason.clone(),
+        });
+        Denied(reason)
+    }
+}
+
+impl SyscallInterceptor for CapabilityInterceptor {
+    fn before(&self, pid: Pid, syscall: &Syscall) -> Result<(), Denied> {
+        let config = self
+            .sandbox_manager
+            .get_config(pid)
+            .ok_or_else(|| self.deny(pid, syscall, format!("no sandbox for pid {pid}")))?;
+
+        let (capability, path) = required_capability(syscall);
+        if let Some(capability) = capability {
+            if !config.has_capability(capability) {
+                return Err(self.deny(pid, syscall, format!("missing {capability:?} capability")));
+            }
+        }
+        if let Some(path) = path {
+            if !config.is_path_allowed(path) {
+                return Err(self.deny(pid, syscall, format!("{} is outside the sandbox", path.display())));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn required_capability(syscall: &Syscall) -> (Option<Capability>, Option<&Path>) {
+    match syscall {
+        Syscall::FileExists { path } => (Some(Capability::ReadFile), Some(path.as_path())),
+        Syscall::ReadFile { path } => (Some(Capability::ReadFile), Some(path.as_path())),
+        Syscall::WriteFile { path, .. } => (Some(Capability::WriteFile), Some(path.as_path())),
+        Syscall::SpawnProcess { .. } => (Some(Capability::SpawnProcess), None),
+        Syscall::WaitProcess { .. } => (Some(Capability::SpawnProcess), None),
+        Syscall::GetSystemInfo => (Some(Capability::SystemInfo), None),
+    }
+}
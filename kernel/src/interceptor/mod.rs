@@ -0,0 +1,42 @@
+//! Cross-cutting hooks around syscall execution, analogous to gRPC
+//! server interceptors. `SyscallExecutor` runs an ordered chain of these
+//! on every syscall so policy - auditing, rate limiting, capability
+//! enforcement - lives in one place instead of being scattered across
+//! each syscall arm.
+
+mod audit;
+mod capability;
+mod rate_limit;
+
+use crate::process::Pid;
+use crate::syscall::{Syscall, SyscallResult};
+
+pub use audit::AuditInterceptor;
+pub use capability::CapabilityInterceptor;
+pub use rate_limit::RateLimitInterceptor;
+
+/// Reason a `before` hook refused to let a syscall proceed.
+#[derive(Debug, Clone)]
+pub struct Denied(pub String);
+
+impl std::fmt::Display for Denied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for Denied {}
+
+/// A policy hook run before and after every syscall. Interceptors run in
+/// registration order on `before` and reverse order on `after`; the first
+/// `before` denial short-circuits execution, but every registered
+/// interceptor - including ones whose own `before` never ran - still gets
+/// its `after` call with the final result, so auditing sees every syscall's
+/// allow/deny/cancel outcome regardless of which interceptor denied it.
+pub trait SyscallInterceptor: Send + Sync {
+    fn before(&self, _pid: Pid, _syscall: &Syscall) -> Result<(), Denied> {
+        Ok(())
+    }
+
+    fn after(&self, _pid: Pid, _syscall: &Syscall, _result: &SyscallResult) {}
+}
@@ -0,0 +1,96 @@
+//! Per-PID, per-syscall-class token bucket rate limiting.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::process::Pid;
+use crate::syscall::Syscall;
+
+use super::{Denied, SyscallInterceptor};
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+pub struct RateLimitInterceptor {
+    capacity: f64,
+    refill_per_sec: f64,
+    buckets: Mutex<HashMap<(Pid, &'static str), Bucket>>,
+}
+
+impl RateLimitInterceptor {
+    /// `capacity` tokens per `(pid, syscall class)` bucket, refilling at
+    /// `refill_per_sec` tokens/second.
+    pub fn new(capacity: f64, refill_per_sec: f64) -> Self {
+        Self {
+            capacity,
+            refill_per_sec,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl SyscallInterceptor for RateLimitInterceptor {
+    fn before(&self, pid: Pid, syscall: &Syscall) -> Result<(), Denied> {
+        let key = (pid, syscall.kind());
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(key).or_insert_with(|| Bucket {
+            tokens: self.capacity,
+            last_refill: Instant::now(),
+        });
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens < 1.0 {
+            return Err(Denied(format!(
+                "rate limit exceeded for {} on pid {pid}",
+                syscall.kind()
+            )));
+        }
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_up_to_capacity_then_denies() {
+        let limiter = RateLimitInterceptor::new(3.0, 0.0);
+        for _ in 0..3 {
+            assert!(limiter.before(1, &Syscall::GetSystemInfo).is_ok());
+        }
+        assert!(limiter.before(1, &Syscall::GetSystemInfo).is_err());
+    }
+
+    #[test]
+    fn buckets_are_independent_per_pid_and_syscall_class() {
+        let limiter = RateLimitInterceptor::new(1.0, 0.0);
+        assert!(limiter.before(1, &Syscall::GetSystemInfo).is_ok());
+        // Different pid: its own bucket, untouched by pid 1's.
+        assert!(limiter.before(2, &Syscall::GetSystemInfo).is_ok());
+        // Same pid, different syscall class: also its own bucket.
+        assert!(limiter
+            .before(1, &Syscall::FileExists { path: "/tmp".into() })
+            .is_ok());
+        // Pid 1's `GetSystemInfo` bucket is the one that's now empty.
+        assert!(limiter.before(1, &Syscall::GetSystemInfo).is_err());
+    }
+
+    #[test]
+    fn refills_over_time() {
+        let limiter = RateLimitInterceptor::new(1.0, 1000.0);
+        assert!(limiter.before(1, &Syscall::GetSystemInfo).is_ok());
+        assert!(limiter.before(1, &Syscall::GetSystemInfo).is_err());
+        // At 1000 tokens/sec a full token refills well within 50ms.
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(limiter.before(1, &Syscall::GetSystemInfo).is_ok());
+    }
+}
@@ -0,0 +1,58 @@
+//! In-process IPC registry between the kernel and the AI service.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::process::Pid;
+
+/// A named, in-memory byte-oriented channel.
+struct Channel {
+    buffer: Vec<Vec<u8>>,
+}
+
+#[derive(Clone)]
+pub struct IPCManager {
+    channels: Arc<Mutex<HashMap<String, Channel>>>,
+}
+
+impl IPCManager {
+    pub fn new() -> Self {
+        Self {
+            channels: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn create_channel(&self, name: &str) {
+        self.channels
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert_with(|| Channel { buffer: Vec::new() });
+    }
+
+    pub fn send(&self, name: &str, message: Vec<u8>) {
+        if let Some(channel) = self.channels.lock().unwrap().get_mut(name) {
+            channel.buffer.push(message);
+        }
+    }
+
+    pub fn channel_count(&self) -> usize {
+        self.channels.lock().unwrap().len()
+    }
+
+    /// Names of every known channel, for introspection.
+    pub fn channel_names(&self) -> Vec<String> {
+        self.channels.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// PID is accepted for future per-process channel scoping; unused today.
+    pub fn channels_for(&self, _pid: Pid) -> usize {
+        self.channel_count()
+    }
+}
+
+impl Default for IPCManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
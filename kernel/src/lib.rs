@@ -0,0 +1,25 @@
+//! AI-OS Kernel library
+//!
+//! Exposes the kernel subsystems (process, memory, IPC, sandboxing, syscalls)
+//! and the gRPC front door used by the AI service to drive them.
+
+mod context;
+mod events;
+mod grpc;
+mod interceptor;
+mod ipc;
+mod memory;
+mod process;
+mod reaper;
+mod sandbox;
+mod syscall;
+
+pub use context::SyscallContext;
+pub use events::{EventBus, EventFilter, EventKind, EventKindTag, KernelEvent};
+pub use grpc::start_grpc_server;
+pub use interceptor::{AuditInterceptor, CapabilityInterceptor, Denied, RateLimitInterceptor, SyscallInterceptor};
+pub use ipc::IPCManager;
+pub use memory::MemoryManager;
+pub use process::{Pid, ProcessManager};
+pub use sandbox::{Capability, SandboxConfig, SandboxManager};
+pub use syscall::{Syscall, SyscallExecutor, SyscallResult};
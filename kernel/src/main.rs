@@ -11,11 +11,13 @@
 use log::info;
 use std::error::Error;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use ai_os_kernel::{
     ProcessManager, MemoryManager, IPCManager,
     SandboxManager, SandboxConfig, Capability,
-    SyscallExecutor, Syscall, start_grpc_server
+    SyscallExecutor, SyscallInterceptor, AuditInterceptor, CapabilityInterceptor, RateLimitInterceptor,
+    Syscall, SyscallContext, EventBus, start_grpc_server
 };
 
 #[tokio::main]
@@ -29,26 +31,43 @@ async fn main() -> Result<(), Box<dyn Error>> {
     info!("================================================");
     
     // Initialize kernel subsystems
+    info!("Initializing event bus...");
+    // Shared by every subsystem below so process/sandbox/capability/memory
+    // lifecycle changes are visible to `SubscribeEvents` callers in real
+    // time instead of only through polling the introspection RPCs.
+    let event_bus = EventBus::default();
+
     info!("Initializing memory manager...");
-    let _memory_manager = MemoryManager::new();
-    
+    let memory_manager = MemoryManager::new(event_bus.clone());
+
     info!("Initializing process manager...");
-    let process_manager = ProcessManager::new();
-    
+    let process_manager = ProcessManager::new(event_bus.clone());
+
     info!("Initializing IPC system...");
-    let _ipc_manager = IPCManager::new();
-    
+    let ipc_manager = IPCManager::new();
+
     info!("Initializing sandbox manager...");
-    let sandbox_manager = SandboxManager::new();
-    
+    let sandbox_manager = SandboxManager::new(event_bus.clone(), process_manager.clone());
+
     info!("Initializing syscall executor...");
-    let syscall_executor = SyscallExecutor::new(sandbox_manager.clone());
+    // Capability enforcement runs first so a denied syscall never reaches
+    // the rate limiter or the audit log's "allowed" bookkeeping; audit
+    // runs last so it's the first to see (and log) the final outcome.
+    // Kept as a concrete `Arc` (not just `Arc<dyn SyscallInterceptor>`) so
+    // the introspection service can also read its per-PID syscall counts.
+    let audit = Arc::new(AuditInterceptor::new());
+    let interceptors: Vec<Arc<dyn SyscallInterceptor>> = vec![
+        Arc::new(CapabilityInterceptor::new(sandbox_manager.clone(), event_bus.clone())),
+        Arc::new(RateLimitInterceptor::new(32.0, 8.0)),
+        audit.clone(),
+    ];
+    let syscall_executor = SyscallExecutor::new(process_manager.clone(), interceptors);
     
     info!("✅ Kernel initialization complete");
     info!("================================================");
     
     // Demo: Create a test process with sandboxing
-    demo_sandboxed_execution(&process_manager, &sandbox_manager, &syscall_executor);
+    demo_sandboxed_execution(&process_manager, &sandbox_manager, &syscall_executor).await;
     
     info!("Kernel entering main loop...");
     info!("Press Ctrl+C to exit");
@@ -58,9 +77,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let grpc_syscall_executor = syscall_executor.clone();
     let grpc_process_manager = process_manager.clone();
     let grpc_sandbox_manager = sandbox_manager.clone();
-    
+    let grpc_ipc_manager = ipc_manager.clone();
+    let grpc_memory_manager = memory_manager.clone();
+    let grpc_audit = audit.clone();
+    let grpc_event_bus = event_bus.clone();
+
     info!("Starting gRPC server on {}", grpc_addr);
-    
+
     // Spawn gRPC server as a background task
     tokio::spawn(async move {
         if let Err(e) = start_grpc_server(
@@ -68,6 +91,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
             grpc_syscall_executor,
             grpc_process_manager,
             grpc_sandbox_manager,
+            grpc_ipc_manager,
+            grpc_memory_manager,
+            grpc_audit,
+            grpc_event_bus,
         )
         .await
         {
@@ -86,7 +113,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
 }
 
 /// Demonstration of sandboxed execution
-fn demo_sandboxed_execution(
+async fn demo_sandboxed_execution(
     process_manager: &ProcessManager,
     sandbox_manager: &SandboxManager,
     syscall_executor: &SyscallExecutor,
@@ -101,37 +128,43 @@ fn demo_sandboxed_execution(
     let mut sandbox_config = SandboxConfig::standard(pid);
     sandbox_config.allow_path(PathBuf::from("/tmp"));
     sandbox_manager.create_sandbox(sandbox_config);
-    
+
+    // Outside of a real gRPC call there's no caller deadline to honor, so
+    // the demo just uses a context with no deadline and that never
+    // cancels.
+    let ctx = SyscallContext::new();
+
     // Test 1: Allowed file read (should succeed)
     info!("\n[Test 1] Attempting allowed file operation...");
-    let result = syscall_executor.execute(
-        pid,
-        Syscall::FileExists { path: PathBuf::from("/tmp/test.txt") }
-    );
+    let result = syscall_executor
+        .execute(pid, Syscall::FileExists { path: PathBuf::from("/tmp/test.txt") }, &ctx)
+        .await;
     info!("Result: {:?}", result);
-    
+
     // Test 2: Blocked file read (should fail)
     info!("\n[Test 2] Attempting blocked file operation...");
-    let result = syscall_executor.execute(
-        pid,
-        Syscall::ReadFile { path: PathBuf::from("/etc/passwd") }
-    );
+    let result = syscall_executor
+        .execute(pid, Syscall::ReadFile { path: PathBuf::from("/etc/passwd") }, &ctx)
+        .await;
     info!("Result: {:?}", result);
-    
+
     // Test 3: Missing capability (should fail)
     info!("\n[Test 3] Attempting operation without capability...");
-    let result = syscall_executor.execute(
-        pid,
-        Syscall::SpawnProcess {
-            command: "echo".to_string(),
-            args: vec!["hello".to_string()]
-        }
-    );
+    let result = syscall_executor
+        .execute(
+            pid,
+            Syscall::SpawnProcess {
+                command: "echo".to_string(),
+                args: vec!["hello".to_string()],
+            },
+            &ctx,
+        )
+        .await;
     info!("Result: {:?}", result);
-    
+
     // Test 4: System info (should succeed)
     info!("\n[Test 4] Attempting allowed system info...");
-    let result = syscall_executor.execute(pid, Syscall::GetSystemInfo);
+    let result = syscall_executor.execute(pid, Syscall::GetSystemInfo, &ctx).await;
     info!("Result: {:?}", result);
     
     info!("-----------------------------------");
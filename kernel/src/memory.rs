@@ -0,0 +1,60 @@
+//! Minimal memory accounting for the kernel.
+//!
+//! Real allocation happens in user space; the kernel only tracks the
+//! high-level numbers sandboxes and the introspection service need.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::events::{EventBus, EventKind};
+
+/// Allocation level past which a `MemoryPressure` event is published on
+/// every further allocation, so subscribers can react before the kernel
+/// actually runs out of room.
+const DEFAULT_PRESSURE_THRESHOLD_BYTES: u64 = 512 * 1024 * 1024;
+
+#[derive(Clone)]
+pub struct MemoryManager {
+    allocated_bytes: Arc<AtomicU64>,
+    pressure_threshold_bytes: u64,
+    event_bus: EventBus,
+}
+
+/// Snapshot of kernel-wide memory usage.
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryStats {
+    pub allocated_bytes: u64,
+}
+
+impl MemoryManager {
+    pub fn new(event_bus: EventBus) -> Self {
+        Self {
+            allocated_bytes: Arc::new(AtomicU64::new(0)),
+            pressure_threshold_bytes: DEFAULT_PRESSURE_THRESHOLD_BYTES,
+            event_bus,
+        }
+    }
+
+    pub fn record_allocation(&self, bytes: u64) {
+        let allocated = self.allocated_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if allocated >= self.pressure_threshold_bytes {
+            self.event_bus.publish(EventKind::MemoryPressure { allocated_bytes: allocated });
+        }
+    }
+
+    pub fn record_free(&self, bytes: u64) {
+        self.allocated_bytes.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> MemoryStats {
+        MemoryStats {
+            allocated_bytes: self.allocated_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for MemoryManager {
+    fn default() -> Self {
+        Self::new(EventBus::default())
+    }
+}
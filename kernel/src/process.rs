@@ -0,0 +1,148 @@
+//! Process bookkeeping.
+//!
+//! `ProcessManager` tracks the logical processes the kernel knows about.
+//! It spawns OS processes only when `Syscall::SpawnProcess` asks it to
+//! (see [`ProcessManager::spawn`]), and it is the shared registry that
+//! both the syscall executor and the sandbox manager key off of.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::events::{EventBus, EventKind};
+use crate::reaper::ProcessSupervisor;
+
+/// Process identifier, scoped to this kernel instance.
+pub type Pid = u32;
+
+#[derive(Debug, Clone)]
+struct ProcessInfo {
+    name: String,
+    priority: u8,
+}
+
+/// Read-only snapshot of one process, for introspection.
+#[derive(Debug, Clone)]
+pub struct ProcessView {
+    pub pid: Pid,
+    pub name: String,
+    pub priority: u8,
+}
+
+/// Shared handle to the kernel's process table.
+#[derive(Clone)]
+pub struct ProcessManager {
+    processes: Arc<Mutex<HashMap<Pid, ProcessInfo>>>,
+    next_pid: Arc<AtomicU32>,
+    supervisor: ProcessSupervisor,
+    /// The OS PID most recently spawned on behalf of each kernel `Pid`, and
+    /// its inverse - lets the sandbox lifecycle (which only knows the
+    /// kernel `Pid`) find the real process to signal or to notice has
+    /// exited on its own.
+    os_pid_by_pid: Arc<Mutex<HashMap<Pid, u32>>>,
+    pid_by_os_pid: Arc<Mutex<HashMap<u32, Pid>>>,
+    event_bus: EventBus,
+}
+
+impl ProcessManager {
+    pub fn new(event_bus: EventBus) -> Self {
+        Self {
+            processes: Arc::new(Mutex::new(HashMap::new())),
+            next_pid: Arc::new(AtomicU32::new(1)),
+            supervisor: ProcessSupervisor::new(event_bus.clone()),
+            os_pid_by_pid: Arc::new(Mutex::new(HashMap::new())),
+            pid_by_os_pid: Arc::new(Mutex::new(HashMap::new())),
+            event_bus,
+        }
+    }
+
+    /// Spawns `command` as a supervised child of the kernel's logical
+    /// `pid` and returns its OS PID. The exit status becomes available
+    /// through [`ProcessManager::wait`], and the association is
+    /// remembered so the sandbox lifecycle can later find this OS process
+    /// from `pid` alone (see [`ProcessManager::os_pid_for`]).
+    pub fn spawn(&self, pid: Pid, command: &str, args: &[String]) -> io::Result<u32> {
+        let os_pid = self.supervisor.spawn(command, args)?;
+        self.os_pid_by_pid.lock().unwrap().insert(pid, os_pid);
+        self.pid_by_os_pid.lock().unwrap().insert(os_pid, pid);
+        Ok(os_pid)
+    }
+
+    /// Blocks until the child with the given OS PID exits, returning its
+    /// exit code.
+    pub async fn wait(&self, os_pid: u32) -> i32 {
+        self.supervisor.wait(os_pid).await
+    }
+
+    /// Asks the child with the given OS PID to exit gracefully, escalating
+    /// to an unconditional kill if it hasn't within `timeout`. Returns the
+    /// exit code recorded for it (see [`ProcessSupervisor::terminate`]).
+    pub async fn terminate(&self, os_pid: u32, timeout: Duration) -> i32 {
+        self.supervisor.terminate(os_pid, timeout).await
+    }
+
+    /// The OS PID most recently spawned on behalf of kernel `pid`, if any.
+    pub fn os_pid_for(&self, pid: Pid) -> Option<u32> {
+        self.os_pid_by_pid.lock().unwrap().get(&pid).copied()
+    }
+
+    /// The kernel `Pid` that spawned the given OS process, if any.
+    pub fn pid_for_os_pid(&self, os_pid: u32) -> Option<Pid> {
+        self.pid_by_os_pid.lock().unwrap().get(&os_pid).copied()
+    }
+
+    /// Number of children reaped so far, for introspection/diagnostics.
+    pub fn zombies_reaped(&self) -> usize {
+        ProcessSupervisor::zombies_reaped()
+    }
+
+    /// Registers a new logical process and returns its PID.
+    pub fn create_process(&self, name: String, priority: u8) -> Pid {
+        let pid = self.next_pid.fetch_add(1, Ordering::SeqCst);
+        self.processes
+            .lock()
+            .unwrap()
+            .insert(pid, ProcessInfo { name, priority });
+        self.event_bus.publish(EventKind::ProcessCreated { pid });
+        pid
+    }
+
+    pub fn exists(&self, pid: Pid) -> bool {
+        self.processes.lock().unwrap().contains_key(&pid)
+    }
+
+    pub fn remove_process(&self, pid: Pid) {
+        self.processes.lock().unwrap().remove(&pid);
+    }
+
+    /// Snapshot of a single process, for introspection.
+    pub fn get(&self, pid: Pid) -> Option<ProcessView> {
+        self.processes.lock().unwrap().get(&pid).map(|info| ProcessView {
+            pid,
+            name: info.name.clone(),
+            priority: info.priority,
+        })
+    }
+
+    /// Snapshot of every known process, for introspection.
+    pub fn list(&self) -> Vec<ProcessView> {
+        self.processes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(&pid, info)| ProcessView {
+                pid,
+                name: info.name.clone(),
+                priority: info.priority,
+            })
+            .collect()
+    }
+}
+
+impl Default for ProcessManager {
+    fn default() -> Self {
+        Self::new(EventBus::default())
+    }
+}
@@ -0,0 +1,377 @@
+//! Async process reaping.
+//!
+//! Spawned children are reaped without a global SIGCHLD handler: each
+//! child's pidfd (Linux 5.3+, via `pidfd_open`) is registered with the
+//! tokio reactor and we await its readability, then `waitid(P_PIDFD, ...)`
+//! to collect the status. On kernels where `pidfd_open` returns `ENOSYS`
+//! we fall back to a single SIGCHLD-driven task that scans outstanding
+//! PIDs with `waitpid(WNOHANG)`.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Once};
+
+use tokio::sync::Notify;
+
+use crate::events::{EventBus, EventKind};
+
+/// Count of children successfully reaped. `AtomicUsize`, not `u64`, so
+/// 32-bit targets (where `u64` atomics are emulated with a lock) stay
+/// correct and lock-free.
+static ZOMBIES_REAPED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct ExitTable {
+    statuses: HashMap<u32, i32>,
+    notify: HashMap<u32, Arc<Notify>>,
+}
+
+/// Supervises spawned children and reports their exit status
+/// asynchronously, independent of how the kernel running this process
+/// reaps children under the hood.
+#[derive(Clone)]
+pub struct ProcessSupervisor {
+    exits: Arc<Mutex<ExitTable>>,
+    fallback_pending: Arc<Mutex<HashSet<u32>>>,
+    fallback_started: Arc<Once>,
+    event_bus: EventBus,
+}
+
+impl ProcessSupervisor {
+    pub fn new(event_bus: EventBus) -> Self {
+        Self {
+            exits: Arc::new(Mutex::new(ExitTable::default())),
+            fallback_pending: Arc::new(Mutex::new(HashSet::new())),
+            fallback_started: Arc::new(Once::new()),
+            event_bus,
+        }
+    }
+
+    pub fn zombies_reaped() -> usize {
+        ZOMBIES_REAPED.load(Ordering::Relaxed)
+    }
+
+    /// Spawns `command` and begins supervising it for exit. Returns the OS
+    /// PID immediately; the exit status becomes available once `wait`
+    /// resolves for that PID.
+    pub fn spawn(&self, command: &str, args: &[String]) -> io::Result<u32> {
+        let mut child = tokio::process::Command::new(command)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(false)
+            .spawn()?;
+        let os_pid = child
+            .id()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "child already reaped"))?;
+        self.notify_for(os_pid);
+
+        #[cfg(target_os = "linux")]
+        {
+            match linux::open_pidfd(os_pid) {
+                Ok(fd) => {
+                    let this = self.clone();
+                    // `child` is moved in and held for the duration of the
+                    // supervision, not reaped through directly: dropping a
+                    // live `tokio::process::Child` hands it to tokio's own
+                    // orphan reaper, which would race our `waitid(P_PIDFD,
+                    // ...)` below for the same pid and could leave us with
+                    // `ECHILD` and a bogus exit code.
+                    tokio::spawn(async move { this.supervise_pidfd(os_pid, fd, child).await });
+                }
+                Err(e) if e.raw_os_error() == Some(libc::ENOSYS) => {
+                    log::warn!(
+                        "pidfd_open unavailable (kernel predates 5.3); \
+                         falling back to a SIGCHLD-driven reaper for pid {os_pid}"
+                    );
+                    self.fallback_pending.lock().unwrap().insert(os_pid);
+                    self.ensure_fallback_reaper();
+                    // The SIGCHLD reaper above does the actual
+                    // `waitpid(WNOHANG)` reaping; this task only holds
+                    // `child` alive until that's happened, so tokio's own
+                    // orphan reaper doesn't also race to reap it on drop.
+                    let this = self.clone();
+                    tokio::spawn(async move {
+                        this.wait(os_pid).await;
+                        drop(child);
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let this = self.clone();
+            tokio::spawn(async move {
+                if let Ok(status) = child.wait().await {
+                    this.record_exit(os_pid, status.code().unwrap_or(-1));
+                }
+            });
+        }
+
+        Ok(os_pid)
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn supervise_pidfd(&self, os_pid: u32, fd: std::os::unix::io::RawFd, _child: tokio::process::Child) {
+        let guard = linux::PidFdGuard(fd);
+        let async_fd = match tokio::io::unix::AsyncFd::new(guard) {
+            Ok(f) => f,
+            Err(e) => {
+                log::error!("failed to register pidfd for pid {os_pid} with the reactor: {e}");
+                self.record_exit(os_pid, -1);
+                return;
+            }
+        };
+        // The pidfd becomes readable exactly once, the moment the process
+        // exits, so a single `readable()` await is all reaping needs. Any
+        // waiter blocked in `wait(os_pid)` needs *some* exit recorded no
+        // matter what happens here, or it hangs forever - so even the
+        // "the reactor couldn't tell us" error path gets a synthetic -1.
+        let code = match async_fd.readable().await {
+            Ok(guard) => linux::reap_via_pidfd(guard.get_ref().0).unwrap_or(-1),
+            Err(e) => {
+                log::error!("failed to poll pidfd for pid {os_pid}: {e}");
+                -1
+            }
+        };
+        self.record_exit(os_pid, code);
+        // `_child` is dropped here, after the pid has already been reaped
+        // above via `waitid`, so tokio's orphan reaper finds nothing left
+        // to do.
+    }
+
+    fn notify_for(&self, os_pid: u32) -> Arc<Notify> {
+        self.exits
+            .lock()
+            .unwrap()
+            .notify
+            .entry(os_pid)
+            .or_insert_with(|| Arc::new(Notify::new()))
+            .clone()
+    }
+
+    fn record_exit(&self, os_pid: u32, code: i32) {
+        let notify = {
+            let mut table = self.exits.lock().unwrap();
+            table.statuses.insert(os_pid, code);
+            table.notify.get(&os_pid).cloned()
+        };
+        ZOMBIES_REAPED.fetch_add(1, Ordering::Relaxed);
+        self.fallback_pending.lock().unwrap().remove(&os_pid);
+        self.event_bus.publish(EventKind::ProcessExited { os_pid, exit_code: code });
+        if let Some(notify) = notify {
+            notify.notify_waiters();
+        }
+    }
+
+    /// Lazily starts the single SIGCHLD-driven reaper task used when
+    /// `pidfd_open` isn't available. One shared task serves every
+    /// fallback child, mirroring how a process-wide SIGCHLD handler
+    /// would behave.
+    #[cfg(target_os = "linux")]
+    fn ensure_fallback_reaper(&self) {
+        self.fallback_started.call_once(|| {
+            let this = self.clone();
+            tokio::spawn(async move { this.run_fallback_reaper().await });
+        });
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn run_fallback_reaper(&self) {
+        let mut sigchld = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::child()) {
+            Ok(s) => s,
+            Err(e) => {
+                log::error!("failed to install SIGCHLD handler, fallback reaping disabled: {e}");
+                return;
+            }
+        };
+        loop {
+            sigchld.recv().await;
+            let pending: Vec<u32> = self.fallback_pending.lock().unwrap().iter().copied().collect();
+            for os_pid in pending {
+                if let Some(code) = linux::try_waitpid_nohang(os_pid) {
+                    self.record_exit(os_pid, code);
+                }
+            }
+        }
+    }
+
+    /// Blocks until `os_pid`'s exit status is available.
+    pub async fn wait(&self, os_pid: u32) -> i32 {
+        loop {
+            let notify = self.notify_for(os_pid);
+            let notified = notify.notified();
+            tokio::pin!(notified);
+            // Registers this waiter with `notify` *now*, before the state
+            // check below, so a `record_exit` that lands in between is
+            // still observed as a stored wake permit instead of being
+            // lost - `Notified` only starts listening once polled, and
+            // polling happens here, not when `notified()` was called.
+            notified.as_mut().enable();
+            if let Some(code) = self.exits.lock().unwrap().statuses.get(&os_pid).copied() {
+                return code;
+            }
+            notified.await;
+        }
+    }
+
+    /// Asks `os_pid` to exit gracefully (`SIGTERM`), gives it up to
+    /// `timeout` to do so, then escalates to `SIGKILL` and waits for the
+    /// (now unavoidable) exit. Either way, returns the exit code recorded
+    /// for the process: the one reaped off it, or -1 if reaping failed. A
+    /// process whose exit is already recorded is not signalled again.
+    #[cfg(unix)]
+    pub async fn terminate(&self, os_pid: u32, timeout: std::time::Duration) -> i32 {
+        if let Some(code) = self.exits.lock().unwrap().statuses.get(&os_pid).copied() {
+            return code;
+        }
+        // SAFETY: `os_pid` is a child this process spawned; `SIGTERM` only
+        // requests termination, it doesn't reap anything itself.
+        unsafe { libc::kill(os_pid as libc::pid_t, libc::SIGTERM) };
+        tokio::select! {
+            code = self.wait(os_pid) => code,
+            _ = tokio::time::sleep(timeout) => {
+                // SAFETY: same `os_pid`; `SIGKILL` can't be caught or
+                // ignored, so the process is guaranteed to exit.
+                unsafe { libc::kill(os_pid as libc::pid_t, libc::SIGKILL) };
+                self.wait(os_pid).await
+            }
+        }
+    }
+
+    /// Non-unix targets have no signal to send; the best we can do is wait
+    /// for the natural exit.
+    #[cfg(not(unix))]
+    pub async fn terminate(&self, os_pid: u32, _timeout: std::time::Duration) -> i32 {
+        self.wait(os_pid).await
+    }
+}
+
+impl Default for ProcessSupervisor {
+    fn default() -> Self {
+        Self::new(EventBus::default())
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::io;
+    use std::os::unix::io::{AsRawFd, RawFd};
+
+    /// Owns a pidfd for the duration of one reap; closed on drop so a
+    /// failed reap can't leak the descriptor.
+    pub(super) struct PidFdGuard(pub RawFd);
+
+    impl AsRawFd for PidFdGuard {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0
+        }
+    }
+
+    impl Drop for PidFdGuard {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.0) };
+        }
+    }
+
+    pub(super) fn open_pidfd(os_pid: u32) -> io::Result<RawFd> {
+        // SAFETY: `pidfd_open(2)` with no flags. `os_pid` is a child we
+        // just spawned, so it cannot have been reaped by anything else
+        // yet and the returned fd is ours alone.
+        let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, os_pid, 0) };
+        if fd < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(fd as RawFd)
+        }
+    }
+
+    pub(super) fn reap_via_pidfd(fd: RawFd) -> io::Result<i32> {
+        let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+        // SAFETY: `fd` is a pidfd we opened above and still own.
+        let rc = unsafe { libc::waitid(libc::P_PIDFD, fd as libc::id_t, &mut info, libc::WEXITED) };
+        if rc != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // `si_status` holds the exit code for `CLD_EXITED`, or the
+        // terminating signal number otherwise; callers only need "some
+        // exit code", so we don't distinguish further here.
+        Ok(unsafe { info.si_status() })
+    }
+
+    pub(super) fn try_waitpid_nohang(os_pid: u32) -> Option<i32> {
+        let mut status: libc::c_int = 0;
+        // SAFETY: `os_pid` is a child this process spawned; `WNOHANG`
+        // keeps the shared reaper task from ever stalling on one pid.
+        let rc = unsafe { libc::waitpid(os_pid as libc::pid_t, &mut status, libc::WNOHANG) };
+        if rc == os_pid as libc::pid_t {
+            Some(unsafe { libc::WEXITSTATUS(status) })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn wait_returns_immediately_once_exit_is_already_recorded() {
+        let supervisor = ProcessSupervisor::new(EventBus::default());
+        supervisor.record_exit(4242, 7);
+        assert_eq!(supervisor.wait(4242).await, 7);
+    }
+
+    // Regression for the lost-wakeup race: `record_exit` landing between
+    // `wait`'s status check and its first poll of `notified` used to be
+    // silently dropped (no permit stored), hanging `wait` forever. With
+    // `enable()` called before the check, the permit is always there by
+    // the time `record_exit` can possibly run. A multi-threaded runtime
+    // and no synchronization between the two tasks is what actually
+    // exercises the race window; bounding with a timeout turns "hangs
+    // forever" into a failing assertion instead of a stuck test suite.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn wait_observes_an_exit_recorded_concurrently() {
+        for os_pid in 0..200u32 {
+            let supervisor = ProcessSupervisor::new(EventBus::default());
+            let waiter = supervisor.clone();
+            let recorder = supervisor.clone();
+            let handle = tokio::spawn(async move { waiter.wait(os_pid).await });
+            recorder.record_exit(os_pid, 5);
+            let code = tokio::time::timeout(std::time::Duration::from_secs(2), handle)
+                .await
+                .expect("wait() should not hang when the exit races the check")
+                .unwrap();
+            assert_eq!(code, 5);
+        }
+    }
+
+    #[tokio::test]
+    async fn spawn_reports_the_real_exit_code() {
+        let supervisor = ProcessSupervisor::new(EventBus::default());
+        let os_pid = supervisor
+            .spawn("/bin/sh", &["-c".to_string(), "exit 7".to_string()])
+            .expect("spawn should succeed");
+        assert_eq!(supervisor.wait(os_pid).await, 7);
+    }
+
+    #[tokio::test]
+    async fn terminate_kills_a_process_that_ignores_sigterm() {
+        let supervisor = ProcessSupervisor::new(EventBus::default());
+        // `trap '' TERM` makes the shell ignore SIGTERM, forcing
+        // `terminate` down its SIGKILL escalation path.
+        let os_pid = supervisor
+            .spawn("/bin/sh", &["-c".to_string(), "trap '' TERM; sleep 30".to_string()])
+            .expect("spawn should succeed");
+        let code = supervisor.terminate(os_pid, std::time::Duration::from_millis(200)).await;
+        // A process killed by SIGKILL has no real exit code; any value
+        // `wait` returns here just needs to be "resolved", not hung.
+        let _ = code;
+    }
+}
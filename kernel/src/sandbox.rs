@@ -0,0 +1,420 @@
+//! Sandbox capability model and lifecycle.
+//!
+//! A sandbox is the kernel's record of what a single process is allowed to
+//! do: which syscall capabilities it holds and which filesystem paths it
+//! may touch. Sandboxes also carry a containerd-style lifecycle
+//! (`Created -> Running -> Stopped -> Deleted`) so a remote controller can
+//! drive them through the sandbox gRPC service rather than only through
+//! the in-process demo path.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use crate::events::{EventBus, EventKind};
+use crate::process::{Pid, ProcessManager};
+
+/// An action a sandboxed process may be permitted to perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    ReadFile,
+    WriteFile,
+    SpawnProcess,
+    NetworkAccess,
+    SystemInfo,
+}
+
+/// The lifecycle state of a sandbox, mirroring the containerd sandbox API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxState {
+    Created,
+    Running,
+    Stopped,
+    Deleted,
+}
+
+impl SandboxState {
+    /// Stable, uppercase name used on the wire (gRPC responses, logs).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SandboxState::Created => "CREATED",
+            SandboxState::Running => "RUNNING",
+            SandboxState::Stopped => "STOPPED",
+            SandboxState::Deleted => "DELETED",
+        }
+    }
+}
+
+/// Capabilities and path restrictions for a single process.
+#[derive(Debug, Clone)]
+pub struct SandboxConfig {
+    pub pid: Pid,
+    pub capabilities: Vec<Capability>,
+    pub allowed_paths: Vec<PathBuf>,
+}
+
+impl SandboxConfig {
+    /// A minimal sandbox: no capabilities, no paths.
+    pub fn new(pid: Pid) -> Self {
+        Self {
+            pid,
+            capabilities: Vec::new(),
+            allowed_paths: Vec::new(),
+        }
+    }
+
+    /// The default sandbox granted to ordinary processes: read-only file
+    /// access plus system info, but no spawning or network access.
+    pub fn standard(pid: Pid) -> Self {
+        Self {
+            pid,
+            capabilities: vec![Capability::ReadFile, Capability::SystemInfo],
+            allowed_paths: Vec::new(),
+        }
+    }
+
+    pub fn allow_path(&mut self, path: PathBuf) -> &mut Self {
+        self.allowed_paths.push(path);
+        self
+    }
+
+    pub fn grant(&mut self, capability: Capability) -> &mut Self {
+        if !self.capabilities.contains(&capability) {
+            self.capabilities.push(capability);
+        }
+        self
+    }
+
+    pub fn has_capability(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+
+    pub fn is_path_allowed(&self, path: &Path) -> bool {
+        self.allowed_paths.iter().any(|allowed| path.starts_with(allowed))
+    }
+}
+
+/// A live sandbox: its config plus lifecycle bookkeeping.
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    pub id: String,
+    pub config: SandboxConfig,
+    pub state: SandboxState,
+    pub created_at: SystemTime,
+    pub exited_at: Option<SystemTime>,
+    pub exit_code: Option<i32>,
+}
+
+/// Error returned when a lifecycle transition is requested out of order,
+/// e.g. starting a sandbox that was never created or deleting one that is
+/// still running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub sandbox_id: String,
+    pub from: SandboxState,
+    pub attempted: &'static str,
+}
+
+impl std::fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "sandbox {} cannot {} from state {:?}",
+            self.sandbox_id, self.attempted, self.from
+        )
+    }
+}
+
+impl std::error::Error for InvalidTransition {}
+
+/// Shared handle to the kernel's sandbox table.
+#[derive(Clone)]
+pub struct SandboxManager {
+    sandboxes: Arc<RwLock<HashMap<String, Sandbox>>>,
+    by_pid: Arc<RwLock<HashMap<Pid, String>>>,
+    /// Per-sandbox exit notification, used by `wait` so callers can block
+    /// asynchronously instead of polling `get`.
+    exit_notify: Arc<RwLock<HashMap<String, Arc<tokio::sync::Notify>>>>,
+    event_bus: EventBus,
+}
+
+impl SandboxManager {
+    /// `process_manager` lets the sandbox lifecycle notice, on its own,
+    /// when the OS process behind a sandbox exits - so `WaitSandbox`
+    /// reflects the real exit rather than only ever resolving after an
+    /// explicit `StopSandbox` call.
+    pub fn new(event_bus: EventBus, process_manager: ProcessManager) -> Self {
+        let this = Self {
+            sandboxes: Arc::new(RwLock::new(HashMap::new())),
+            by_pid: Arc::new(RwLock::new(HashMap::new())),
+            exit_notify: Arc::new(RwLock::new(HashMap::new())),
+            event_bus,
+        };
+        this.watch_process_exits(process_manager);
+        this
+    }
+
+    /// Auto-stops a sandbox, recording the real exit code, the moment its
+    /// underlying OS process exits on its own rather than through an
+    /// explicit `StopSandbox` call.
+    fn watch_process_exits(&self, process_manager: ProcessManager) {
+        let this = self.clone();
+        let mut events = this.event_bus.subscribe();
+        tokio::spawn(async move {
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+                if let EventKind::ProcessExited { os_pid, exit_code } = event.kind {
+                    if let Some(pid) = process_manager.pid_for_os_pid(os_pid) {
+                        if let Some(sandbox_id) = this.sandbox_id_for_pid(pid) {
+                            // Already stopped (e.g. by an explicit
+                            // `StopSandbox`) just means we lost the race;
+                            // nothing to do.
+                            let _ = this.stop(&sandbox_id, exit_code);
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// The sandbox currently tracking kernel `pid`, if any.
+    pub fn sandbox_id_for_pid(&self, pid: Pid) -> Option<String> {
+        self.by_pid.read().unwrap().get(&pid).cloned()
+    }
+
+    /// Creates a sandbox in the `Created` state for the given config and
+    /// returns its generated ID. Both the demo and the `CreateSandbox` RPC
+    /// create sandboxes through this.
+    pub fn create_sandbox(&self, config: SandboxConfig) -> String {
+        let id = format!("sandbox-{}", config.pid);
+        self.insert_sandbox(id.clone(), config);
+        self.event_bus.publish(EventKind::SandboxStateChanged {
+            sandbox_id: id.clone(),
+            state: SandboxState::Created.as_str(),
+        });
+        id
+    }
+
+    fn insert_sandbox(&self, id: String, config: SandboxConfig) {
+        let pid = config.pid;
+        let sandbox = Sandbox {
+            id: id.clone(),
+            config,
+            state: SandboxState::Created,
+            created_at: SystemTime::now(),
+            exited_at: None,
+            exit_code: None,
+        };
+        self.sandboxes.write().unwrap().insert(id.clone(), sandbox);
+        self.by_pid.write().unwrap().insert(pid, id.clone());
+        self.exit_notify
+            .write()
+            .unwrap()
+            .insert(id, Arc::new(tokio::sync::Notify::new()));
+    }
+
+    pub fn get_config(&self, pid: Pid) -> Option<SandboxConfig> {
+        let by_pid = self.by_pid.read().unwrap();
+        let id = by_pid.get(&pid)?;
+        self.sandboxes.read().unwrap().get(id).map(|s| s.config.clone())
+    }
+
+    pub fn get(&self, id: &str) -> Option<Sandbox> {
+        self.sandboxes.read().unwrap().get(id).cloned()
+    }
+
+    /// Snapshot of every known sandbox, for introspection.
+    pub fn list(&self) -> Vec<Sandbox> {
+        self.sandboxes.read().unwrap().values().cloned().collect()
+    }
+
+    /// Transitions a sandbox from `Created` to `Running`.
+    pub fn start(&self, id: &str) -> Result<(), InvalidTransition> {
+        self.transition(id, "start", |state| *state == SandboxState::Created, SandboxState::Running)
+    }
+
+    /// Transitions a sandbox to `Stopped`, recording its exit.
+    pub fn stop(&self, id: &str, exit_code: i32) -> Result<(), InvalidTransition> {
+        let mut sandboxes = self.sandboxes.write().unwrap();
+        let sandbox = sandboxes
+            .get_mut(id)
+            .ok_or_else(|| InvalidTransition {
+                sandbox_id: id.to_string(),
+                from: SandboxState::Deleted,
+                attempted: "stop",
+            })?;
+        if sandbox.state != SandboxState::Running && sandbox.state != SandboxState::Created {
+            return Err(InvalidTransition {
+                sandbox_id: id.to_string(),
+                from: sandbox.state,
+                attempted: "stop",
+            });
+        }
+        sandbox.state = SandboxState::Stopped;
+        sandbox.exited_at = Some(SystemTime::now());
+        sandbox.exit_code = Some(exit_code);
+        drop(sandboxes);
+        if let Some(notify) = self.exit_notify.read().unwrap().get(id) {
+            notify.notify_waiters();
+        }
+        self.event_bus.publish(EventKind::SandboxStateChanged {
+            sandbox_id: id.to_string(),
+            state: SandboxState::Stopped.as_str(),
+        });
+        Ok(())
+    }
+
+    /// Blocks until `id` transitions to `Stopped`, then returns its exit
+    /// code and exit timestamp. Resolves immediately if the sandbox is
+    /// already stopped by the time this is called.
+    pub async fn wait(&self, id: &str) -> Result<(i32, SystemTime), InvalidTransition> {
+        loop {
+            let notify = {
+                let map = self.exit_notify.read().unwrap();
+                match map.get(id) {
+                    Some(n) => n.clone(),
+                    None => {
+                        return Err(InvalidTransition {
+                            sandbox_id: id.to_string(),
+                            from: SandboxState::Deleted,
+                            attempted: "wait",
+                        })
+                    }
+                }
+            };
+            // Register interest before checking state so a stop() that
+            // races with this check can't be missed: `enable()` stores a
+            // wake permit immediately, rather than only once `notified`
+            // is first polled (which would be after the state check,
+            // too late to catch a `notify_waiters()` landing in between).
+            let notified = notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if let Some(sandbox) = self.sandboxes.read().unwrap().get(id) {
+                if let (SandboxState::Stopped, Some(code), Some(at)) =
+                    (sandbox.state, sandbox.exit_code, sandbox.exited_at)
+                {
+                    return Ok((code, at));
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// Removes a stopped sandbox from the table permanently.
+    pub fn delete(&self, id: &str) -> Result<(), InvalidTransition> {
+        let mut sandboxes = self.sandboxes.write().unwrap();
+        let sandbox = sandboxes
+            .get(id)
+            .ok_or_else(|| InvalidTransition {
+                sandbox_id: id.to_string(),
+                from: SandboxState::Deleted,
+                attempted: "delete",
+            })?;
+        if sandbox.state != SandboxState::Stopped {
+            return Err(InvalidTransition {
+                sandbox_id: id.to_string(),
+                from: sandbox.state,
+                attempted: "delete",
+            });
+        }
+        let pid = sandbox.config.pid;
+        sandboxes.remove(id);
+        drop(sandboxes);
+        self.by_pid.write().unwrap().remove(&pid);
+        self.exit_notify.write().unwrap().remove(id);
+        self.event_bus.publish(EventKind::SandboxStateChanged {
+            sandbox_id: id.to_string(),
+            state: SandboxState::Deleted.as_str(),
+        });
+        Ok(())
+    }
+
+    fn transition(
+        &self,
+        id: &str,
+        attempted: &'static str,
+        allowed_from: impl Fn(&SandboxState) -> bool,
+        to: SandboxState,
+    ) -> Result<(), InvalidTransition> {
+        let mut sandboxes = self.sandboxes.write().unwrap();
+        let sandbox = sandboxes
+            .get_mut(id)
+            .ok_or_else(|| InvalidTransition {
+                sandbox_id: id.to_string(),
+                from: SandboxState::Deleted,
+                attempted,
+            })?;
+        if !allowed_from(&sandbox.state) {
+            return Err(InvalidTransition {
+                sandbox_id: id.to_string(),
+                from: sandbox.state,
+                attempted,
+            });
+        }
+        sandbox.state = to;
+        drop(sandboxes);
+        self.event_bus.publish(EventKind::SandboxStateChanged {
+            sandbox_id: id.to_string(),
+            state: to.as_str(),
+        });
+        Ok(())
+    }
+}
+
+impl Default for SandboxManager {
+    fn default() -> Self {
+        let event_bus = EventBus::default();
+        Self::new(event_bus.clone(), ProcessManager::new(event_bus))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn wait_returns_immediately_once_already_stopped() {
+        let manager = SandboxManager::default();
+        let id = manager.create_sandbox(SandboxConfig::new(1));
+        manager.start(&id).unwrap();
+        manager.stop(&id, 9).unwrap();
+        let (code, _) = manager.wait(&id).await.unwrap();
+        assert_eq!(code, 9);
+    }
+
+    // Regression for the lost-wakeup race: a `stop()` landing between
+    // `wait`'s state check and its first poll of `notified` used to be
+    // silently dropped, hanging `wait` forever. `enable()` stores the
+    // wake permit before the check, so a concurrently racing `stop()`
+    // is always observed.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn wait_observes_a_stop_recorded_concurrently() {
+        for i in 0..200u32 {
+            let manager = SandboxManager::default();
+            let id = manager.create_sandbox(SandboxConfig::new(i));
+            manager.start(&id).unwrap();
+            let waiter = manager.clone();
+            let wait_id = id.clone();
+            let handle = tokio::spawn(async move { waiter.wait(&wait_id).await });
+            manager.stop(&id, 3).unwrap();
+            let (code, _) = tokio::time::timeout(std::time::Duration::from_secs(2), handle)
+                .await
+                .expect("wait() should not hang when stop() races the check")
+                .unwrap()
+                .unwrap();
+            assert_eq!(code, 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_sandbox_id_is_invalid_transition() {
+        let manager = SandboxManager::default();
+        assert!(manager.start("no-such-sandbox").is_err());
+    }
+}
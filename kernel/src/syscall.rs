@@ -0,0 +1,148 @@
+//! Syscall definitions and the executor that runs them through the
+//! interceptor chain before doing any real work.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::context::SyscallContext;
+use crate::interceptor::SyscallInterceptor;
+use crate::process::{Pid, ProcessManager};
+
+/// A request from the AI service to do something on its behalf.
+#[derive(Debug, Clone)]
+pub enum Syscall {
+    FileExists { path: PathBuf },
+    ReadFile { path: PathBuf },
+    WriteFile { path: PathBuf, data: Vec<u8> },
+    SpawnProcess { command: String, args: Vec<String> },
+    /// Blocks until the OS process with the given PID (as returned by a
+    /// prior `SpawnProcess`) exits, then yields its exit code.
+    WaitProcess { pid: u32 },
+    GetSystemInfo,
+}
+
+impl Syscall {
+    /// A short, stable name for the syscall's class, used by interceptors
+    /// that key policy (rate limits, audit entries) per syscall kind
+    /// rather than per exact arguments.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Syscall::FileExists { .. } => "FileExists",
+            Syscall::ReadFile { .. } => "ReadFile",
+            Syscall::WriteFile { .. } => "WriteFile",
+            Syscall::SpawnProcess { .. } => "SpawnProcess",
+            Syscall::WaitProcess { .. } => "WaitProcess",
+            Syscall::GetSystemInfo => "GetSystemInfo",
+        }
+    }
+}
+
+/// Outcome of executing a `Syscall`.
+#[derive(Debug, Clone)]
+pub enum SyscallResult {
+    Success { data: Option<Vec<u8>> },
+    Error { message: String },
+    /// The caller's `SyscallContext` was cancelled before the syscall
+    /// finished, e.g. because the gRPC client disconnected.
+    Cancelled,
+    /// The caller's deadline elapsed before the syscall finished.
+    DeadlineExceeded,
+}
+
+impl SyscallResult {
+    fn ok() -> Self {
+        SyscallResult::Success { data: None }
+    }
+
+    fn ok_with(data: Vec<u8>) -> Self {
+        SyscallResult::Success { data: Some(data) }
+    }
+
+    fn denied(message: impl Into<String>) -> Self {
+        SyscallResult::Error { message: message.into() }
+    }
+}
+
+/// Executes syscalls on behalf of sandboxed processes. Every syscall runs
+/// through the interceptor chain first - capability enforcement, rate
+/// limiting, and auditing all live there - so this only has to do the
+/// actual work.
+#[derive(Clone)]
+pub struct SyscallExecutor {
+    process_manager: ProcessManager,
+    interceptors: Vec<Arc<dyn SyscallInterceptor>>,
+}
+
+impl SyscallExecutor {
+    pub fn new(process_manager: ProcessManager, interceptors: Vec<Arc<dyn SyscallInterceptor>>) -> Self {
+        Self {
+            process_manager,
+            interceptors,
+        }
+    }
+
+    pub async fn execute(&self, pid: Pid, syscall: Syscall, ctx: &SyscallContext) -> SyscallResult {
+        if ctx.cancel.is_cancelled() {
+            return SyscallResult::Cancelled;
+        }
+        if ctx.is_expired() {
+            return SyscallResult::DeadlineExceeded;
+        }
+
+        let mut denial = None;
+        for interceptor in &self.interceptors {
+            if let Err(reason) = interceptor.before(pid, &syscall) {
+                denial = Some(reason);
+                break;
+            }
+        }
+
+        let result = match denial {
+            Some(reason) => SyscallResult::denied(reason.0),
+            None => self.run(pid, syscall.clone(), ctx).await,
+        };
+
+        // Every interceptor gets its `after` call regardless of where the
+        // `before` chain stopped, so audit (and any other interceptor
+        // tracking outcomes) sees allow/deny/cancel for every syscall, not
+        // just the ones whose own `before` happened to run first.
+        for interceptor in self.interceptors.iter().rev() {
+            interceptor.after(pid, &syscall, &result);
+        }
+
+        result
+    }
+
+    async fn run(&self, pid: Pid, syscall: Syscall, ctx: &SyscallContext) -> SyscallResult {
+        match syscall {
+            Syscall::FileExists { path } => SyscallResult::ok_with(vec![path.exists() as u8]),
+            // `tokio::fs` (not `std::fs`) so the future actually yields to
+            // the runtime instead of running the blocking syscall to
+            // completion on first poll, which would let it starve the
+            // `guard` cancel/deadline arms below.
+            Syscall::ReadFile { path } => match ctx.guard(tokio::fs::read(path)).await {
+                Ok(Ok(contents)) => SyscallResult::ok_with(contents),
+                Ok(Err(e)) => SyscallResult::denied(e.to_string()),
+                Err(aborted) => aborted,
+            },
+            Syscall::WriteFile { path, data } => match ctx.guard(tokio::fs::write(path, data)).await {
+                Ok(Ok(())) => SyscallResult::ok(),
+                Ok(Err(e)) => SyscallResult::denied(e.to_string()),
+                Err(aborted) => aborted,
+            },
+            Syscall::SpawnProcess { command, args } => match ctx
+                .guard(async { self.process_manager.spawn(pid, &command, &args) })
+                .await
+            {
+                Ok(Ok(os_pid)) => SyscallResult::ok_with(os_pid.to_le_bytes().to_vec()),
+                Ok(Err(e)) => SyscallResult::denied(e.to_string()),
+                Err(aborted) => aborted,
+            },
+            Syscall::WaitProcess { pid: os_pid } => match ctx.guard(self.process_manager.wait(os_pid)).await {
+                Ok(code) => SyscallResult::ok_with(code.to_le_bytes().to_vec()),
+                Err(aborted) => aborted,
+            },
+            Syscall::GetSystemInfo => SyscallResult::ok_with(std::env::consts::OS.as_bytes().to_vec()),
+        }
+    }
+}